@@ -1,5 +1,7 @@
 use std::{collections::HashSet, fmt::Debug};
 
+use serde::{Serialize, Serializer};
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Difference<T> {
     Empty,
@@ -9,6 +11,38 @@ pub enum Difference<T> {
     Added(T),
 }
 
+/// Mirrors [`Difference`] for serialization: a stable, internally-tagged
+/// shape (`{"kind": "modified", "old": ..., "new": ...}`) that's easy for
+/// downstream tooling (CI, PR bots) to parse regardless of variant.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum DifferenceRepr<'a, T> {
+    Empty,
+    Equal { value: &'a T },
+    Removed { value: &'a T },
+    Modified { old: &'a T, new: &'a T },
+    Added { value: &'a T },
+}
+
+impl<T> Serialize for Difference<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let repr = match self {
+            Difference::Empty => DifferenceRepr::Empty,
+            Difference::Equal(value) => DifferenceRepr::Equal { value },
+            Difference::Removed(value) => DifferenceRepr::Removed { value },
+            Difference::Modified { old, new } => DifferenceRepr::Modified { old, new },
+            Difference::Added(value) => DifferenceRepr::Added { value },
+        };
+        repr.serialize(serializer)
+    }
+}
+
 impl<T> Difference<T>
 where
     T: PartialEq,
@@ -49,7 +83,11 @@ impl<T> Difference<T>
 where
     T: Eq + std::hash::Hash + Clone,
 {
-    fn unstable_diff_vec(a: Vec<T>, b: Vec<T>) -> Vec<Difference<T>> {
+    /// Diff two sequences by membership alone, ignoring their original
+    /// ordering. Cheap, but clumps all `Removed` entries before all `Added`
+    /// ones and can't represent the real position of each change. Kept
+    /// around as a fast fallback for callers that don't care about order.
+    pub fn unstable_diff_vec(a: Vec<T>, b: Vec<T>) -> Vec<Difference<T>> {
         let a: HashSet<T> = HashSet::from_iter(a.into_iter());
         let b: HashSet<T> = HashSet::from_iter(b.into_iter());
 
@@ -71,13 +109,51 @@ where
 
 impl<T> Difference<T>
 where
-    T: Eq + Ord + std::hash::Hash + Clone,
+    T: PartialEq + Clone,
 {
-    pub fn diff_vec(a: Vec<T>, b: Vec<T>) -> Vec<Difference<T>> {
-        let mut diff = Self::unstable_diff_vec(a, b);
-        diff.sort();
+    /// Diff two sequences with a longest-common-subsequence alignment, so
+    /// `Removed`/`Added`/`Equal` entries interleave in their original
+    /// positions instead of being grouped by membership.
+    pub fn lcs_diff_vec(a: Vec<T>, b: Vec<T>) -> Vec<Difference<T>> {
+        let n = a.len();
+        let m = b.len();
+
+        let mut dp = vec![vec![0usize; m + 1]; n + 1];
+        for i in 1..=n {
+            for j in 1..=m {
+                dp[i][j] = if a[i - 1] == b[j - 1] {
+                    dp[i - 1][j - 1] + 1
+                } else {
+                    dp[i - 1][j].max(dp[i][j - 1])
+                };
+            }
+        }
+
+        let mut diff = Vec::with_capacity(n.max(m));
+        let (mut i, mut j) = (n, m);
+        while i > 0 || j > 0 {
+            if i > 0 && j > 0 && a[i - 1] == b[j - 1] {
+                diff.push(Difference::Equal(a[i - 1].clone()));
+                i -= 1;
+                j -= 1;
+            } else if j == 0 || (i > 0 && dp[i - 1][j] >= dp[i][j - 1]) {
+                diff.push(Difference::Removed(a[i - 1].clone()));
+                i -= 1;
+            } else {
+                diff.push(Difference::Added(b[j - 1].clone()));
+                j -= 1;
+            }
+        }
+
+        diff.reverse();
         diff
     }
+
+    /// Diff two sequences, preserving their original ordering. This is the
+    /// default used by [`crate::PackageDiff::diff`] for dependency lists.
+    pub fn diff_vec(a: Vec<T>, b: Vec<T>) -> Vec<Difference<T>> {
+        Self::lcs_diff_vec(a, b)
+    }
 }
 
 #[cfg(test)]
@@ -117,4 +193,44 @@ mod tests {
         ];
         assert_eq!(Difference::diff_vec(a, b), expected);
     }
+
+    #[test]
+    fn test_lcs_diff_vec_preserves_order() {
+        // Unlike the unstable, set-based diff, the LCS diff interleaves
+        // removals and additions at their original positions instead of
+        // clumping all removals before all additions.
+        let a = vec!["a", "x", "b", "y", "c"];
+        let b = vec!["a", "b", "z", "c"];
+        let expected = vec![
+            Difference::Equal("a"),
+            Difference::Removed("x"),
+            Difference::Equal("b"),
+            Difference::Added("z"),
+            Difference::Removed("y"),
+            Difference::Equal("c"),
+        ];
+        assert_eq!(Difference::lcs_diff_vec(a, b), expected);
+    }
+
+    #[test]
+    fn test_unstable_diff_vec_clumps_by_membership() {
+        let a = vec!["a", "b", "c"];
+        let b = vec!["b", "c", "d"];
+        let diff = Difference::unstable_diff_vec(a, b);
+        assert_eq!(diff.len(), 4);
+        assert!(diff.contains(&Difference::Removed("a")));
+        assert!(diff.contains(&Difference::Added("d")));
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(
+            serde_json::to_value(Difference::Equal(1)).unwrap(),
+            serde_json::json!({"kind": "equal", "value": 1})
+        );
+        assert_eq!(
+            serde_json::to_value(Difference::Modified { old: 1, new: 2 }).unwrap(),
+            serde_json::json!({"kind": "modified", "old": 1, "new": 2})
+        );
+    }
 }
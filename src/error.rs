@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+/// Errors that can occur while loading a `Cargo.lock`, whether from disk or
+/// from a git revision via `git show <revspec>:<path>`.
+#[derive(Debug, thiserror::Error)]
+pub enum LoadLockError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {path} as a Cargo.lock: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("failed to spawn `git show {spec}`: {source}")]
+    GitSpawn {
+        spec: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("git show {spec} failed: {stderr}")]
+    GitFailed { spec: String, stderr: String },
+
+    #[error("git show {spec} produced invalid UTF-8: {source}")]
+    GitUtf8 {
+        spec: String,
+        #[source]
+        source: std::string::FromUtf8Error,
+    },
+}
@@ -1,9 +1,19 @@
 mod difference;
+mod error;
+mod version;
 
 use colored::Colorize;
 use difference::Difference;
-use serde::Deserialize;
-use std::{collections::HashMap, fmt::Debug, fs::read_to_string, path::Path};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::Command,
+};
+pub use error::LoadLockError;
+pub use version::VersionChange;
 
 #[derive(Deserialize, Debug, Eq, PartialEq, Clone, Hash)]
 pub struct Package {
@@ -15,10 +25,11 @@ pub struct Package {
     dependencies: Vec<String>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Serialize)]
 pub struct PackageDiff {
     pub name: String,
     pub version: Difference<String>,
+    pub version_change: Option<VersionChange>,
     pub source: Difference<String>,
     pub checksum: Difference<String>,
     pub dependencies: Vec<Difference<String>>,
@@ -41,19 +52,33 @@ impl PackageDiff {
         if a.name != b.name {
             panic!("diffing different packages is not supported");
         }
+        let version = Difference::diff(a.version, b.version);
+        let version_change = Self::classify_version_change(&version);
         PackageDiff {
             name: a.name,
-            version: Difference::diff(a.version, b.version),
+            version,
+            version_change,
             source: Difference::diff_opt(a.source, b.source),
             checksum: Difference::diff_opt(a.checksum, b.checksum),
             dependencies: Difference::diff_vec(a.dependencies, b.dependencies),
         }
     }
 
+    /// Classify a version [`Difference`] into a [`VersionChange`], falling
+    /// back to `None` when the change isn't a `Modified` pair of valid
+    /// semver versions.
+    fn classify_version_change(version: &Difference<String>) -> Option<VersionChange> {
+        match version {
+            Difference::Modified { old, new } => VersionChange::parse_and_classify(old, new),
+            _ => None,
+        }
+    }
+
     pub fn added(p: Package) -> PackageDiff {
         PackageDiff {
             name: p.name,
             version: Difference::Added(p.version),
+            version_change: None,
             source: p
                 .source
                 .map_or(Difference::Empty, |source| Difference::Added(source)),
@@ -72,6 +97,7 @@ impl PackageDiff {
         PackageDiff {
             name: p.name,
             version: Difference::Removed(p.version),
+            version_change: None,
             source: p
                 .source
                 .map_or(Difference::Empty, |source| Difference::Removed(source)),
@@ -104,7 +130,12 @@ impl PackageDiff {
             Difference::Equal(version) => println!(" version = \"{}\"", version),
             Difference::Modified { old, new } => {
                 println!("{}", format!("-version = \"{}\"", old).red());
-                println!("{}", format!("+version = \"{}\"", new).green());
+                match &self.version_change {
+                    Some(change) => {
+                        println!("{}", format!("+version = \"{}\" ({})", new, change).green())
+                    }
+                    None => println!("{}", format!("+version = \"{}\"", new).green()),
+                }
             }
             Difference::Added(version) => {
                 println!("{}", format!("+version = \"{}\"", version).green())
@@ -189,13 +220,60 @@ pub struct CargoLock {
 }
 
 impl CargoLock {
-    pub fn load_lock<P: AsRef<Path>>(path: P) -> Self {
-        let contents = read_to_string(path).expect("reading should succeed");
-        toml::from_str(&contents).expect("parsing should succeed")
+    /// Load a `Cargo.lock` from either a filesystem path or a git revision
+    /// spec of the form `<revspec>:<path>` (e.g. `HEAD~1:Cargo.lock`), the
+    /// same syntax `git show` itself accepts.
+    pub fn load(source: &str) -> Result<Self, LoadLockError> {
+        match source.split_once(':') {
+            Some((revspec, path)) => Self::load_lock_at_revision(revspec, path),
+            None => Self::load_lock(source),
+        }
+    }
+
+    pub fn load_lock<P: AsRef<Path>>(path: P) -> Result<Self, LoadLockError> {
+        let path = path.as_ref();
+        let contents = read_to_string(path).map_err(|source| LoadLockError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents, path.to_path_buf())
+    }
+
+    /// Load a `Cargo.lock` as it existed at `revspec`, by shelling out to
+    /// `git show <revspec>:<path>`.
+    pub fn load_lock_at_revision<P: AsRef<Path>>(
+        revspec: &str,
+        path: P,
+    ) -> Result<Self, LoadLockError> {
+        let path = path.as_ref();
+        let spec = format!("{}:{}", revspec, path.display());
+
+        let output = Command::new("git")
+            .args(["show", &spec])
+            .output()
+            .map_err(|source| LoadLockError::GitSpawn {
+                spec: spec.clone(),
+                source,
+            })?;
+
+        if !output.status.success() {
+            return Err(LoadLockError::GitFailed {
+                spec,
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        let contents = String::from_utf8(output.stdout)
+            .map_err(|source| LoadLockError::GitUtf8 { spec, source })?;
+        Self::parse(&contents, path.to_path_buf())
+    }
+
+    fn parse(contents: &str, path: PathBuf) -> Result<Self, LoadLockError> {
+        toml::from_str(contents).map_err(|source| LoadLockError::Parse { path, source })
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct CargoLockDiff {
     pub version: Difference<u8>,
     pub package: Vec<PackageDiff>,
@@ -266,6 +344,52 @@ impl CargoLockDiff {
             self.package[self.package.len() - 1].pretty_print_package(verbose);
         }
     }
+
+    /// Build the changelog-style summary lines for this diff, grouped into
+    /// `Adding`/`Removing`/`Updating` sections. A package is only listed
+    /// when its own version was added, removed, or changed — a package
+    /// whose version is unchanged is skipped here even if its source,
+    /// checksum, or resolved dependencies differ, since there's no
+    /// meaningful `vX -> vY` line to show for it. Packages are already
+    /// sorted by name (see [`CargoLockDiff::difference`]), so each section
+    /// comes out sorted too.
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut adding = Vec::new();
+        let mut removing = Vec::new();
+        let mut updating = Vec::new();
+
+        for package in self.package.iter() {
+            if package.is_equal_or_empty() {
+                continue;
+            }
+
+            match &package.version {
+                Difference::Added(version) => {
+                    adding.push(format!("Adding   {} v{}", package.name, version))
+                }
+                Difference::Removed(version) => {
+                    removing.push(format!("Removing {} v{}", package.name, version))
+                }
+                Difference::Modified { old, new } => {
+                    updating.push(format!("Updating {} v{} -> v{}", package.name, old, new))
+                }
+                Difference::Equal(_) | Difference::Empty => {}
+            }
+        }
+
+        let mut lines = Vec::with_capacity(adding.len() + removing.len() + updating.len());
+        lines.extend(adding);
+        lines.extend(removing);
+        lines.extend(updating);
+        lines
+    }
+
+    /// Print the changelog-style summary, one line per changed package.
+    pub fn print_summary(&self) {
+        for line in self.summary_lines() {
+            println!("{}", line);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -337,6 +461,7 @@ mod test {
                 old: "1.15.0".to_string(),
                 new: "1.34.0".to_string(),
             },
+            version_change: Some(VersionChange::Minor),
             source: Difference::Equal(
                 "registry+https://github.com/rust-lang/crates.io-index".to_string(),
             ),
@@ -345,26 +470,47 @@ mod test {
                 new: "d0c014766411e834f7af5b8f4cf46257aab4036ca95e9d2c144a10f59ad6f5b9".to_string(),
             },
             dependencies: vec![
-                Difference::Removed("memchr".to_string()),
-                Difference::Removed("once_cell".to_string()),
-                Difference::Removed("winapi".to_string()),
+                Difference::Added("backtrace".to_string()),
                 Difference::Equal("bytes".to_string()),
                 Difference::Equal("libc".to_string()),
+                Difference::Removed("memchr".to_string()),
                 Difference::Equal("mio".to_string()),
                 Difference::Equal("num_cpus".to_string()),
+                Difference::Removed("once_cell".to_string()),
                 Difference::Equal("parking_lot".to_string()),
                 Difference::Equal("pin-project-lite".to_string()),
                 Difference::Equal("signal-hook-registry".to_string()),
-                Difference::Equal("tokio-macros".to_string()),
-                Difference::Added("backtrace".to_string()),
                 Difference::Added("socket2".to_string()),
+                Difference::Equal("tokio-macros".to_string()),
                 Difference::Added("windows-sys 0.48.0".to_string()),
+                Difference::Removed("winapi".to_string()),
             ],
         };
 
         assert_eq!(diff, expected);
     }
 
+    #[test]
+    fn test_package_diff_version_change() {
+        let mut old = tokio_1_15_0_lock();
+        old.version = "1.0.0".to_string();
+        let mut new = tokio_1_15_0_lock();
+        new.version = "2.0.0".to_string();
+
+        let diff = PackageDiff::diff(old, new);
+        assert_eq!(diff.version_change, Some(VersionChange::Major));
+    }
+
+    #[test]
+    fn test_package_diff_version_change_not_semver() {
+        let mut old = tokio_1_15_0_lock();
+        old.version = "not-semver".to_string();
+        let new = tokio_1_34_0_lock();
+
+        let diff = PackageDiff::diff(old, new);
+        assert_eq!(diff.version_change, None);
+    }
+
     #[test]
     fn test_cargo_lock_diff() {
         let a = CargoLock {
@@ -386,6 +532,7 @@ mod test {
                     old: "1.15.0".to_string(),
                     new: "1.34.0".to_string(),
                 },
+                version_change: Some(VersionChange::Minor),
                 source: Difference::Equal(
                     "registry+https://github.com/rust-lang/crates.io-index".to_string(),
                 ),
@@ -396,24 +543,91 @@ mod test {
                         .to_string(),
                 },
                 dependencies: vec![
-                    Difference::Removed("memchr".to_string()),
-                    Difference::Removed("once_cell".to_string()),
-                    Difference::Removed("winapi".to_string()),
+                    Difference::Added("backtrace".to_string()),
                     Difference::Equal("bytes".to_string()),
                     Difference::Equal("libc".to_string()),
+                    Difference::Removed("memchr".to_string()),
                     Difference::Equal("mio".to_string()),
                     Difference::Equal("num_cpus".to_string()),
+                    Difference::Removed("once_cell".to_string()),
                     Difference::Equal("parking_lot".to_string()),
                     Difference::Equal("pin-project-lite".to_string()),
                     Difference::Equal("signal-hook-registry".to_string()),
-                    Difference::Equal("tokio-macros".to_string()),
-                    Difference::Added("backtrace".to_string()),
                     Difference::Added("socket2".to_string()),
+                    Difference::Equal("tokio-macros".to_string()),
                     Difference::Added("windows-sys 0.48.0".to_string()),
+                    Difference::Removed("winapi".to_string()),
                 ],
             }],
         };
 
         assert_eq!(diff, expected);
     }
+
+    #[test]
+    fn test_cargo_lock_diff_summary() {
+        let a = CargoLock {
+            version: 3,
+            package: vec![tokio_1_15_0_lock()],
+        };
+
+        let b = CargoLock {
+            version: 3,
+            package: vec![tokio_1_34_0_lock()],
+        };
+
+        let diff = CargoLockDiff::difference(a, b);
+        assert_eq!(
+            diff.summary_lines(),
+            vec!["Updating tokio v1.15.0 -> v1.34.0".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_cargo_lock_diff_summary_skips_checksum_only_change() {
+        let old = tokio_1_15_0_lock();
+        let mut new = old.clone();
+        new.checksum = Some("a-different-checksum".to_string());
+
+        let diff = CargoLockDiff::difference(
+            CargoLock {
+                version: 3,
+                package: vec![old],
+            },
+            CargoLock {
+                version: 3,
+                package: vec![new],
+            },
+        );
+
+        // The version itself didn't change, so there's no `vX -> vY` to
+        // show even though the package isn't fully equal.
+        assert!(diff.package[0].version.is_equal());
+        assert!(!diff.package[0].is_equal_or_empty());
+        assert!(diff.summary_lines().is_empty());
+    }
+
+    #[test]
+    fn test_load_lock_missing_file() {
+        let err = CargoLock::load_lock("/no/such/Cargo.lock").unwrap_err();
+        assert!(matches!(err, LoadLockError::Io { .. }));
+    }
+
+    #[test]
+    fn test_load_bad_revision() {
+        let err = CargoLock::load("nonexistent-rev:Cargo.lock").unwrap_err();
+        assert!(matches!(err, LoadLockError::GitFailed { .. }));
+    }
+
+    #[test]
+    fn test_package_diff_serialize() {
+        let diff = PackageDiff::diff(tokio_1_15_0_lock(), tokio_1_34_0_lock());
+        let value = serde_json::to_value(&diff).unwrap();
+
+        assert_eq!(value["name"], "tokio");
+        assert_eq!(value["version"]["kind"], "modified");
+        assert_eq!(value["version"]["old"], "1.15.0");
+        assert_eq!(value["version"]["new"], "1.34.0");
+        assert_eq!(value["version_change"], "minor");
+    }
 }
@@ -1,14 +1,29 @@
-use std::path::PathBuf;
+use std::process::ExitCode;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use colored::control::set_override;
 use lock_diff::{CargoLock, CargoLockDiff};
 use pager::Pager;
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum OutputFormat {
+    /// Full `[[package]]`-per-change TOML-like dump.
+    #[default]
+    Pretty,
+    /// Compact, one-line-per-package changelog summary.
+    Summary,
+    /// Structured JSON, for CI pipelines and PR bots.
+    Json,
+}
+
 #[derive(Parser)]
 struct Cli {
-    old: PathBuf,
-    new: PathBuf,
+    /// Path to a `Cargo.lock`, or a git revision spec such as
+    /// `HEAD~1:Cargo.lock`.
+    old: String,
+    /// Path to a `Cargo.lock`, or a git revision spec such as
+    /// `HEAD~1:Cargo.lock`.
+    new: String,
 
     #[arg(long, default_value = "false")]
     no_color: bool,
@@ -18,19 +33,44 @@ struct Cli {
 
     #[arg(long, default_value = "false")]
     no_pager: bool,
+
+    #[arg(long, value_enum, default_value_t = OutputFormat::Pretty)]
+    format: OutputFormat,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
     if !cli.no_pager {
         Pager::new().setup();
     }
 
-    let old_lock = CargoLock::load_lock(cli.old);
-    let new_lock = CargoLock::load_lock(cli.new);
+    let old_lock = match CargoLock::load(&cli.old) {
+        Ok(lock) => lock,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let new_lock = match CargoLock::load(&cli.new) {
+        Ok(lock) => lock,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
 
     set_override(!cli.no_color);
 
-    CargoLockDiff::difference(old_lock, new_lock).pretty_print(cli.verbose);
+    let diff = CargoLockDiff::difference(old_lock, new_lock);
+    match cli.format {
+        OutputFormat::Pretty => diff.pretty_print(cli.verbose),
+        OutputFormat::Summary => diff.print_summary(),
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&diff).expect("serializing should succeed");
+            println!("{}", json);
+        }
+    }
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,118 @@
+use std::fmt::{self, Display};
+
+use semver::Version;
+use serde::Serialize;
+
+/// Classification of a package's version change, as parsed through `semver`.
+///
+/// Falls back to a plain string diff (see [`crate::Difference`]) when either
+/// side of the change isn't valid semver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VersionChange {
+    Major,
+    Minor,
+    Patch,
+    Downgrade,
+    PreRelease,
+}
+
+impl VersionChange {
+    /// Classify the transition from `old` to `new`.
+    pub fn classify(old: &Version, new: &Version) -> VersionChange {
+        if new < old {
+            return VersionChange::Downgrade;
+        }
+
+        if old.major != new.major {
+            return VersionChange::Major;
+        }
+        if old.minor != new.minor {
+            return VersionChange::Minor;
+        }
+        if old.patch != new.patch {
+            return VersionChange::Patch;
+        }
+        if old.pre != new.pre {
+            return VersionChange::PreRelease;
+        }
+
+        // Major/minor/patch/pre-release all match: the only possible
+        // difference left is build metadata, or no difference at all.
+        // `semver::Version`'s ordering compares build metadata only as a
+        // final tiebreaker, so a build-only change whose new build sorts
+        // before the old one has already been reported as a downgrade above.
+        // Neither case is a prerelease transition, so fall back to the most
+        // benign bump.
+        VersionChange::Patch
+    }
+
+    /// Try to classify a version change from two version strings, returning
+    /// `None` when either string isn't valid semver.
+    pub fn parse_and_classify(old: &str, new: &str) -> Option<VersionChange> {
+        let old = Version::parse(old).ok()?;
+        let new = Version::parse(new).ok()?;
+        Some(VersionChange::classify(&old, &new))
+    }
+}
+
+impl Display for VersionChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            VersionChange::Major => "major",
+            VersionChange::Minor => "minor",
+            VersionChange::Patch => "patch",
+            VersionChange::Downgrade => "downgrade",
+            VersionChange::PreRelease => "pre-release",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let v = |s: &str| Version::parse(s).unwrap();
+
+        assert_eq!(
+            VersionChange::classify(&v("1.0.0"), &v("2.0.0")),
+            VersionChange::Major
+        );
+        assert_eq!(
+            VersionChange::classify(&v("1.0.0"), &v("1.1.0")),
+            VersionChange::Minor
+        );
+        assert_eq!(
+            VersionChange::classify(&v("1.0.0"), &v("1.0.1")),
+            VersionChange::Patch
+        );
+        assert_eq!(
+            VersionChange::classify(&v("1.1.0"), &v("1.0.0")),
+            VersionChange::Downgrade
+        );
+        assert_eq!(
+            VersionChange::classify(&v("1.0.0-alpha.1"), &v("1.0.0-alpha.2")),
+            VersionChange::PreRelease
+        );
+    }
+
+    #[test]
+    fn test_classify_build_metadata_only_is_not_prerelease() {
+        let v = |s: &str| Version::parse(s).unwrap();
+
+        // Only the build metadata differs (and the new build sorts after the
+        // old one), so this must not be mistaken for a prerelease transition.
+        assert_eq!(
+            VersionChange::classify(&v("1.0.0+build1"), &v("1.0.0+build2")),
+            VersionChange::Patch
+        );
+    }
+
+    #[test]
+    fn test_parse_and_classify_invalid() {
+        assert_eq!(VersionChange::parse_and_classify("not-semver", "1.0.0"), None);
+    }
+}